@@ -27,23 +27,98 @@
 //! Output HTML
 //!
 //! ```notrust
-//! <a href="/test"><img src="/logo.png" />Rust</a>
+//! <a href="/test"><img src="/logo.png" alt="" />Rust</a>
 //! ```
 
 use lol_html::html_content::{Comment, Element};
-use lol_html::{comments, element, rewrite_str, RewriteStrSettings};
+use lol_html::{comments, element, rewrite_str, HandlerResult, HtmlRewriter, RewriteStrSettings};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AllowedElement<'a> {
     pub name: &'a str,
     pub attributes: Vec<&'a str>,
+    /// Per-attribute URL scheme allow-list, e.g. `href -> ["http", "https", "mailto"]`.
+    ///
+    /// Attributes not present in this map are not scheme-restricted. Values with no
+    /// scheme (relative URLs, anchors) are always allowed. `data:` URLs are handled
+    /// separately and only permitted for a small set of safe image mime types.
+    #[serde(default, borrow)]
+    pub protocols: HashMap<&'a str, Vec<&'a str>>,
+    /// CSS properties permitted in this element's `style` attribute.
+    ///
+    /// An empty list (the default) leaves `style` unrestricted. When non-empty, any
+    /// declaration whose property isn't listed here is dropped, and surviving
+    /// declarations are still checked for unsafe `url(...)` schemes and `expression(...)`.
+    #[serde(default, borrow)]
+    pub allowed_styles: Vec<&'a str>,
+    /// Per-element `class` allow-list. Empty defers to [`Settings::allowed_classes`]; if
+    /// that is also empty, classes are not allow-list restricted.
+    #[serde(default, borrow)]
+    pub allowed_classes: Vec<&'a str>,
+    /// Per-element `class` block-list, checked in addition to [`Settings::forbidden_classes`].
+    #[serde(default, borrow)]
+    pub forbidden_classes: Vec<&'a str>,
+    /// Attributes injected when missing, after attribute filtering, e.g. `alt -> ""` on
+    /// `<img>` so every surviving image has alt text.
+    #[serde(default, borrow)]
+    pub default_attributes: HashMap<&'a str, &'a str>,
+    /// Attributes that must be present, after filtering and default injection, or the
+    /// element's tag is removed and its content kept (content and all are removed if the
+    /// tag is also listed in [`Settings::remove_with_contents`]).
+    #[serde(default, borrow)]
+    pub required_attributes: Vec<&'a str>,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Settings<'a> {
     #[serde(borrow)]
     pub allowed: Vec<AllowedElement<'a>>,
     pub remove_comments: bool,
+    /// Global `class` token allow-list, used by elements that don't set their own
+    /// [`AllowedElement::allowed_classes`]. Empty means no allow-list restriction.
+    #[serde(default, borrow)]
+    pub allowed_classes: Vec<&'a str>,
+    /// Global `class` token block-list, always checked alongside any per-element
+    /// [`AllowedElement::forbidden_classes`].
+    #[serde(default, borrow)]
+    pub forbidden_classes: Vec<&'a str>,
+    /// Disallowed tags whose contents are dropped entirely instead of being unwrapped
+    /// into the output. Without this, a disallowed tag's text and child elements are
+    /// kept in place of the tag itself, which leaks raw `<script>`/`<style>` text and
+    /// promotes `<noscript>` fallback content into the visible document.
+    #[serde(default, borrow)]
+    pub remove_with_contents: Vec<&'a str>,
+    /// When true, forces `rel="nofollow noopener"` on every surviving `<a>` and restricts
+    /// `target` to [`Settings::allowed_frame_targets`], hardening links against tabnabbing.
+    #[serde(default)]
+    pub enforce_link_safety: bool,
+    /// Frame targets permitted on `<a target="...">` when [`Settings::enforce_link_safety`]
+    /// is enabled. A `target` not in this list is stripped. Only takes effect if `target`
+    /// is also listed in `<a>`'s [`AllowedElement::attributes`] — otherwise the ordinary
+    /// attribute allow-list already strips `target` before this restriction ever runs.
+    #[serde(default, borrow)]
+    pub allowed_frame_targets: Vec<&'a str>,
+    /// Whether `id` attributes are honored at all, mirroring HTML Purifier's `Attr.EnableID`.
+    /// When false, `id` is stripped from every element even if listed in its
+    /// [`AllowedElement::attributes`].
+    #[serde(default)]
+    pub enable_id: bool,
+    /// Exact `id` values to reject, e.g. ids the host page's own anchors already use.
+    /// Mirrors HTML Purifier's `Attr.IDBlacklist`.
+    #[serde(default, borrow)]
+    pub id_blacklist: Vec<&'a str>,
+    /// Regex patterns checked against `id` values in addition to [`Settings::id_blacklist`].
+    /// An invalid pattern is ignored rather than rejecting every id. Mirrors HTML Purifier's
+    /// `Attr.IDBlacklistRegexp`.
+    #[serde(default, borrow)]
+    pub id_blacklist_regexp: Vec<&'a str>,
+    /// Prepended to every surviving `id` to namespace user content and prevent it from
+    /// colliding with or hijacking the host page's own anchors, e.g. `"user-"` turns
+    /// `id="foo"` into `id="user-foo"`. Mirrors HTML Purifier's `Attr.IDPrefix`.
+    #[serde(default, borrow)]
+    pub id_prefix: &'a str,
 }
 
 impl<'a> Default for Settings<'a> {
@@ -54,54 +129,132 @@ impl<'a> Default for Settings<'a> {
                 AllowedElement {
                     name: "div",
                     attributes: vec![],
+                    protocols: HashMap::new(),
+                    allowed_styles: vec![],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "b",
                     attributes: vec![],
+                    protocols: HashMap::new(),
+                    allowed_styles: vec![],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "strong",
                     attributes: vec![],
+                    protocols: HashMap::new(),
+                    allowed_styles: vec![],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "i",
                     attributes: vec![],
+                    protocols: HashMap::new(),
+                    allowed_styles: vec![],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "em",
                     attributes: vec![],
+                    protocols: HashMap::new(),
+                    allowed_styles: vec![],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "u",
                     attributes: vec![],
+                    protocols: HashMap::new(),
+                    allowed_styles: vec![],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "a",
                     attributes: vec!["href", "title"],
+                    protocols: HashMap::from([("href", vec!["http", "https", "mailto", "tel", "ftp"])]),
+                    allowed_styles: vec![],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "ul",
                     attributes: vec![],
+                    protocols: HashMap::new(),
+                    allowed_styles: vec![],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "ol",
                     attributes: vec![],
+                    protocols: HashMap::new(),
+                    allowed_styles: vec![],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "li",
                     attributes: vec![],
+                    protocols: HashMap::new(),
+                    allowed_styles: vec![],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "p",
                     attributes: vec!["style"],
+                    protocols: HashMap::new(),
+                    allowed_styles: vec!["color", "background-color", "font-weight", "font-style", "text-align", "text-decoration"],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "br",
                     attributes: vec![],
+                    protocols: HashMap::new(),
+                    allowed_styles: vec![],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "span",
                     attributes: vec!["style"],
+                    protocols: HashMap::new(),
+                    allowed_styles: vec!["color", "background-color", "font-weight", "font-style", "text-align", "text-decoration"],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::new(),
+                    required_attributes: vec![],
                 },
                 AllowedElement {
                     name: "img",
@@ -111,13 +264,201 @@ impl<'a> Default for Settings<'a> {
                         "alt",
                         "src",
                     ],
+                    protocols: HashMap::from([("src", vec!["http", "https"])]),
+                    allowed_styles: vec![],
+                    allowed_classes: vec![],
+                    forbidden_classes: vec![],
+                    default_attributes: HashMap::from([("alt", "")]),
+                    required_attributes: vec!["src"],
                 },
             ],
             remove_comments: true,
+            allowed_classes: vec![],
+            forbidden_classes: vec![],
+            remove_with_contents: vec!["script", "style", "noscript", "iframe", "object", "embed", "template"],
+            enforce_link_safety: false,
+            allowed_frame_targets: vec!["_blank", "_self"],
+            enable_id: false,
+            id_blacklist: vec![],
+            id_blacklist_regexp: vec![],
+            id_prefix: "",
         }
     }
 }
 
+/// Extracts the scheme from a URL-like attribute value, e.g. `"javascript:alert(1)"` -> `Some("javascript")`.
+///
+/// Returns `None` when the value has no scheme (relative URLs, anchors, query strings),
+/// which mirrors the browser rule that a `:` only introduces a scheme when nothing that
+/// could only appear in a path, fragment, or query (`/`, `#`, `?`, `&`) precedes it.
+fn extract_scheme(value: &str) -> Option<&str> {
+    let idx = value.find(':')?;
+    let prefix = &value[..idx];
+    if prefix.is_empty() || prefix.contains(['/', '#', '?', '&']) {
+        return None;
+    }
+    Some(prefix)
+}
+
+/// Whether a `data:` URL is one of the small set of safe inline image types we allow
+/// through even when the attribute's scheme allow-list doesn't mention `data`.
+fn is_safe_data_url(value: &str) -> bool {
+    let lower = value.to_ascii_lowercase();
+    let Some(rest) = lower.strip_prefix("data:") else {
+        return false;
+    };
+    let mime = rest.split([';', ',']).next().unwrap_or("");
+    matches!(
+        mime,
+        "image/png" | "image/gif" | "image/jpeg" | "image/jpg" | "image/webp" | "image/bmp"
+    )
+}
+
+/// Checks a URL-like attribute value against an attribute's `protocols` allow-list.
+///
+/// `None` (no entry in the `protocols` map) means not scheme-restricted. Values with no
+/// scheme are always allowed, and `data:` URLs are always routed through
+/// [`is_safe_data_url`] regardless of what the allow-list contains.
+fn is_allowed_url<S: AsRef<str>>(allowed_schemes: Option<&Vec<S>>, value: &str) -> bool {
+    let Some(allowed_schemes) = allowed_schemes else {
+        return true;
+    };
+    let Some(scheme) = extract_scheme(value) else {
+        return true;
+    };
+    if scheme.eq_ignore_ascii_case("data") {
+        return is_safe_data_url(value);
+    }
+    allowed_schemes.iter().any(|s| s.as_ref().eq_ignore_ascii_case(scheme))
+}
+
+/// Whether a CSS declaration value contains a known injection vector: an `expression(...)`
+/// (old IE script execution) or a `url(...)` pointing at a non-`http(s)` scheme.
+fn is_unsafe_style_value(value: &str) -> bool {
+    let lower = value.to_ascii_lowercase();
+    if lower.contains("expression(") {
+        return true;
+    }
+    for (start, _) in lower.match_indices("url(") {
+        let inner = value[start + 4..].trim_start_matches(['\'', '"', ' ']);
+        if let Some(scheme) = extract_scheme(inner) {
+            if !scheme.eq_ignore_ascii_case("http") && !scheme.eq_ignore_ascii_case("https") {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// Splits a `style` attribute value on top-level `;`, ignoring any `;` inside a quoted
+/// string or a `(...)` argument list (e.g. `url(data:image/png;base64,...)`), so a
+/// semicolon embedded in a declaration's value doesn't fracture it into bogus fragments.
+fn split_style_declarations(value: &str) -> Vec<&str> {
+    let mut declarations = Vec::new();
+    let mut depth = 0u32;
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in value.char_indices() {
+        match c {
+            '\'' | '"' if quote.is_none() => quote = Some(c),
+            c if quote == Some(c) => quote = None,
+            '(' if quote.is_none() => depth += 1,
+            ')' if quote.is_none() && depth > 0 => depth -= 1,
+            ';' if quote.is_none() && depth == 0 => {
+                declarations.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    declarations.push(&value[start..]);
+    declarations
+}
+
+/// Filters a `style` attribute value down to declarations whose property is allow-listed
+/// and whose value isn't flagged by [`is_unsafe_style_value`]. Returns `None` when no
+/// declaration survives, signalling that the attribute should be removed entirely.
+fn sanitize_style<S: AsRef<str>>(allowed_styles: &[S], value: &str) -> Option<String> {
+    if allowed_styles.is_empty() {
+        return Some(value.to_string());
+    }
+    let declarations: Vec<String> = split_style_declarations(value)
+        .into_iter()
+        .filter_map(|decl| {
+            let (prop, val) = decl.split_once(':')?;
+            let (prop, val) = (prop.trim(), val.trim());
+            if prop.is_empty() || val.is_empty() {
+                return None;
+            }
+            if !allowed_styles.iter().any(|p| p.as_ref().eq_ignore_ascii_case(prop)) {
+                return None;
+            }
+            if is_unsafe_style_value(val) {
+                return None;
+            }
+            Some(format!("{}: {}", prop, val))
+        })
+        .collect();
+    if declarations.is_empty() {
+        None
+    } else {
+        Some(format!("{};", declarations.join("; ")))
+    }
+}
+
+/// Filters a `class` attribute value down to tokens that pass the effective allow-list
+/// and block-list for an element. `elem_allowed` (when non-empty) takes precedence over
+/// `global_allowed`; both block-lists always apply together. Returns `None` when no
+/// token survives, signalling that the attribute should be removed.
+fn sanitize_classes<S: AsRef<str>>(
+    elem_allowed: &[S],
+    global_allowed: &[S],
+    elem_forbidden: &[S],
+    global_forbidden: &[S],
+    value: &str,
+) -> Option<String> {
+    let allowed = if elem_allowed.is_empty() { global_allowed } else { elem_allowed };
+    let tokens: Vec<&str> = value
+        .split_whitespace()
+        .filter(|token| {
+            if global_forbidden.iter().any(|c| c.as_ref().eq_ignore_ascii_case(token))
+                || elem_forbidden.iter().any(|c| c.as_ref().eq_ignore_ascii_case(token))
+            {
+                return false;
+            }
+            allowed.is_empty() || allowed.iter().any(|c| c.as_ref().eq_ignore_ascii_case(token))
+        })
+        .collect();
+    if tokens.is_empty() {
+        None
+    } else {
+        Some(tokens.join(" "))
+    }
+}
+
+/// Validates an `id` attribute value against the blacklist/regex rules and, if it survives,
+/// prepends `prefix`. Returns `None` when ids are disabled, the value is empty, it exactly
+/// matches `blacklist`, or it matches any pattern in `blacklist_regexp`, signalling that the
+/// attribute should be removed.
+fn sanitize_id<S: AsRef<str>>(
+    enable_id: bool,
+    blacklist: &[S],
+    blacklist_regexp: &[Regex],
+    prefix: &str,
+    value: &str,
+) -> Option<String> {
+    if !enable_id || value.is_empty() {
+        return None;
+    }
+    if blacklist.iter().any(|b| b.as_ref() == value) {
+        return None;
+    }
+    if blacklist_regexp.iter().any(|re| re.is_match(value)) {
+        return None;
+    }
+    Some(format!("{prefix}{value}"))
+}
+
 /// HTML Purifier
 ///
 /// # Example
@@ -132,43 +473,395 @@ impl<'a> Default for Settings<'a> {
 /// let output = purifier(input, settings);
 /// ```
 pub fn purifier(input: &str, settings: Settings) -> String {
-    let element_handler = |el: &mut Element| {
-        if let Some(find) = settings.allowed.iter().find(|e| e.name.eq(&el.tag_name())) {
-            let remove_attributes: Vec<String> = el
-                .attributes()
-                .iter()
-                .filter(|attr| !find.attributes.contains(&&*attr.name()))
-                .map(|attr| attr.name())
-                .collect();
-            for attr in remove_attributes {
-                el.remove_attribute(&*attr);
+    settings_to_purifier(&settings).purify(input)
+}
+
+/// Builds a one-off [`Purifier`] from borrowed [`Settings`], so [`purifier`] shares its
+/// filtering pipeline with [`Purifier::purify`] instead of keeping a second copy in sync.
+fn settings_to_purifier(settings: &Settings) -> Purifier {
+    let mut builder = Purifier::builder()
+        .remove_comments(settings.remove_comments)
+        .global_allowed_classes(&settings.allowed_classes)
+        .global_forbidden_classes(&settings.forbidden_classes)
+        .remove_with_contents(&settings.remove_with_contents)
+        .enforce_link_safety(settings.enforce_link_safety)
+        .allowed_frame_targets(&settings.allowed_frame_targets)
+        .enable_id(settings.enable_id)
+        .id_blacklist(&settings.id_blacklist)
+        .id_blacklist_regexp(&settings.id_blacklist_regexp)
+        .id_prefix(settings.id_prefix);
+    for elem in &settings.allowed {
+        builder = builder.allow(elem.name, &elem.attributes);
+        for (attribute, schemes) in &elem.protocols {
+            builder = builder.url_schemes(elem.name, attribute, schemes);
+        }
+        builder = builder
+            .allowed_styles(elem.name, &elem.allowed_styles)
+            .allowed_classes(elem.name, &elem.allowed_classes)
+            .forbidden_classes(elem.name, &elem.forbidden_classes);
+        for (attribute, value) in &elem.default_attributes {
+            builder = builder.default_attribute(elem.name, attribute, value);
+        }
+        for attribute in &elem.required_attributes {
+            builder = builder.required_attribute(elem.name, attribute);
+        }
+    }
+    builder.build()
+}
+
+/// Owned, per-tag rules used by [`Purifier`]. Mirrors [`AllowedElement`] field-for-field
+/// except `name` (the tag is the [`Purifier`]'s map key), but with owned `String`s so a [`Purifier`] doesn't borrow from the code that built it
+/// and can be kept around and reused across many [`Purifier::purify`] calls.
+#[derive(Debug, Clone, Default)]
+pub struct OwnedElement {
+    pub attributes: Vec<String>,
+    pub protocols: HashMap<String, Vec<String>>,
+    pub allowed_styles: Vec<String>,
+    pub allowed_classes: Vec<String>,
+    pub forbidden_classes: Vec<String>,
+    pub default_attributes: HashMap<String, String>,
+    pub required_attributes: Vec<String>,
+}
+
+/// A reusable HTML purifier built via [`Purifier::builder`].
+///
+/// Unlike [`purifier`], which takes [`Settings`] by value and rebuilds its lookups on
+/// every call, `Purifier` precomputes a `HashMap` keyed by tag name so repeated
+/// [`Purifier::purify`] calls (e.g. in a server sanitizing many documents) do an O(1)
+/// lookup instead of scanning a `Vec` per element.
+#[derive(Debug, Clone)]
+pub struct Purifier {
+    allowed: HashMap<String, OwnedElement>,
+    remove_comments: bool,
+    allowed_classes: Vec<String>,
+    forbidden_classes: Vec<String>,
+    remove_with_contents: Vec<String>,
+    enforce_link_safety: bool,
+    allowed_frame_targets: Vec<String>,
+    enable_id: bool,
+    id_blacklist: Vec<String>,
+    /// Patterns from [`PurifierBuilder::id_blacklist_regexp`], compiled once in
+    /// [`PurifierBuilder::build`] so `purify`/`purify_writer` never reparse a pattern per
+    /// element.
+    id_blacklist_regexp: Vec<Regex>,
+    id_prefix: String,
+}
+
+impl Purifier {
+    /// Starts a [`PurifierBuilder`] with no tags allowed.
+    pub fn builder() -> PurifierBuilder {
+        PurifierBuilder::default()
+    }
+
+    fn element_handler<'s>(&'s self) -> impl FnMut(&mut Element) -> HandlerResult + 's {
+        move |el: &mut Element| {
+            if let Some(find) = self.allowed.get(&el.tag_name()) {
+                let remove_attributes: Vec<String> = el
+                    .attributes()
+                    .iter()
+                    .filter(|attr| {
+                        !find.attributes.iter().any(|a| a == &attr.name())
+                            || !is_allowed_url(find.protocols.get(attr.name().as_str()), &attr.value())
+                    })
+                    .map(|attr| attr.name())
+                    .collect();
+                for attr in remove_attributes {
+                    el.remove_attribute(&attr);
+                }
+                if let Some(style) = el.get_attribute("style") {
+                    match sanitize_style(&find.allowed_styles, &style) {
+                        Some(sanitized) => el.set_attribute("style", &sanitized).unwrap(),
+                        None => el.remove_attribute("style"),
+                    }
+                }
+                if let Some(class) = el.get_attribute("class") {
+                    let sanitized = sanitize_classes(
+                        &find.allowed_classes,
+                        &self.allowed_classes,
+                        &find.forbidden_classes,
+                        &self.forbidden_classes,
+                        &class,
+                    );
+                    match sanitized {
+                        Some(sanitized) => el.set_attribute("class", &sanitized).unwrap(),
+                        None => el.remove_attribute("class"),
+                    }
+                }
+                if let Some(id) = el.get_attribute("id") {
+                    let sanitized = sanitize_id(
+                        self.enable_id,
+                        &self.id_blacklist,
+                        &self.id_blacklist_regexp,
+                        &self.id_prefix,
+                        &id,
+                    );
+                    match sanitized {
+                        Some(sanitized) => el.set_attribute("id", &sanitized).unwrap(),
+                        None => el.remove_attribute("id"),
+                    }
+                }
+                for (name, value) in &find.default_attributes {
+                    if el.get_attribute(name).is_none() {
+                        el.set_attribute(name, value).unwrap();
+                    }
+                }
+                let missing_required = find
+                    .required_attributes
+                    .iter()
+                    .any(|attr| el.get_attribute(attr).is_none());
+                if missing_required {
+                    if self.remove_with_contents.iter().any(|tag| tag.eq_ignore_ascii_case(&el.tag_name())) {
+                        el.remove();
+                    } else {
+                        el.remove_and_keep_content();
+                    }
+                    return Ok(());
+                }
+                if self.enforce_link_safety && el.tag_name().eq_ignore_ascii_case("a") {
+                    el.set_attribute("rel", "nofollow noopener").unwrap();
+                    if let Some(target) = el.get_attribute("target") {
+                        if !self.allowed_frame_targets.iter().any(|t| t.eq_ignore_ascii_case(&target)) {
+                            el.remove_attribute("target");
+                        }
+                    }
+                }
+            } else if self.remove_with_contents.iter().any(|tag| tag.eq_ignore_ascii_case(&el.tag_name())) {
+                el.remove();
+            } else {
+                el.remove_and_keep_content();
             }
-        } else {
-            el.remove_and_keep_content();
+            Ok(())
         }
-        Ok(())
-    };
+    }
 
-    let comment_handler = |c: &mut Comment| {
-        if settings.remove_comments {
-            c.remove();
+    fn comment_handler<'s>(&'s self) -> impl FnMut(&mut Comment) -> HandlerResult + 's {
+        move |c: &mut Comment| {
+            if self.remove_comments {
+                c.remove();
+            }
+            Ok(())
         }
-        Ok(())
-    };
+    }
 
-    let output = rewrite_str(
-        input,
-        RewriteStrSettings {
-            element_content_handlers: vec![
-                element!("*", element_handler),
-                comments!("*", comment_handler),
-            ],
-            ..RewriteStrSettings::default()
-        },
-    )
-    .unwrap();
+    /// Purifies `input`, returning the sanitized HTML as an owned `String`.
+    pub fn purify(&self, input: &str) -> String {
+        rewrite_str(
+            input,
+            RewriteStrSettings {
+                element_content_handlers: vec![
+                    element!("*", self.element_handler()),
+                    comments!("*", self.comment_handler()),
+                ],
+                ..RewriteStrSettings::default()
+            },
+        )
+        .unwrap()
+    }
+
+    /// Streaming variant of [`Purifier::purify`] that writes sanitized output directly to
+    /// `writer` instead of building an intermediate `String`.
+    pub fn purify_writer<W: std::io::Write>(&self, input: &str, mut writer: W) -> std::io::Result<()> {
+        let io_error = std::rc::Rc::new(std::cell::RefCell::new(None));
+        let io_error_sink = std::rc::Rc::clone(&io_error);
+        let mut rewriter = HtmlRewriter::new(
+            lol_html::Settings {
+                element_content_handlers: vec![
+                    element!("*", self.element_handler()),
+                    comments!("*", self.comment_handler()),
+                ],
+                ..lol_html::Settings::default()
+            },
+            move |chunk: &[u8]| {
+                if io_error_sink.borrow().is_some() {
+                    return;
+                }
+                if let Err(err) = writer.write_all(chunk) {
+                    *io_error_sink.borrow_mut() = Some(err);
+                }
+            },
+        );
+        let rewrite_result = (|| {
+            rewriter.write(input.as_bytes())?;
+            rewriter.end()
+        })()
+        .map_err(|err| std::io::Error::other(err.to_string()));
+        if let Some(err) = io_error.borrow_mut().take() {
+            return Err(err);
+        }
+        rewrite_result
+    }
+}
+
+/// Fluent builder for [`Purifier`], e.g.:
+///
+/// ```
+/// use html_purifier::Purifier;
+///
+/// let purifier = Purifier::builder()
+///     .allow("a", &["href"])
+///     .url_schemes("a", "href", &["http", "https"])
+///     .remove_comments(true)
+///     .build();
+/// let output = purifier.purify(r#"<a href="javascript:alert(1)">Rust</a>"#);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PurifierBuilder {
+    inner: Purifier,
+    /// Raw `id_blacklist_regexp` patterns, compiled into [`Purifier::id_blacklist_regexp`]
+    /// only once, in [`Self::build`].
+    id_blacklist_regexp: Vec<String>,
+}
 
-    output
+impl Default for Purifier {
+    fn default() -> Self {
+        Purifier {
+            allowed: HashMap::new(),
+            remove_comments: true,
+            allowed_classes: vec![],
+            forbidden_classes: vec![],
+            remove_with_contents: vec![
+                "script", "style", "noscript", "iframe", "object", "embed", "template",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            enforce_link_safety: false,
+            allowed_frame_targets: vec!["_blank", "_self"].into_iter().map(String::from).collect(),
+            enable_id: false,
+            id_blacklist: vec![],
+            id_blacklist_regexp: vec![],
+            id_prefix: String::new(),
+        }
+    }
+}
+
+impl PurifierBuilder {
+    fn entry(&mut self, tag: &str) -> &mut OwnedElement {
+        self.inner.allowed.entry(tag.to_string()).or_default()
+    }
+
+    /// Allows `tag`, permitting exactly the attributes listed in `attributes`.
+    pub fn allow(mut self, tag: &str, attributes: &[&str]) -> Self {
+        self.entry(tag).attributes = attributes.iter().map(|a| a.to_string()).collect();
+        self
+    }
+
+    /// Restricts `attribute` on `tag` to the given URL schemes (see [`AllowedElement::protocols`]).
+    pub fn url_schemes(mut self, tag: &str, attribute: &str, schemes: &[&str]) -> Self {
+        self.entry(tag).protocols.insert(
+            attribute.to_string(),
+            schemes.iter().map(|s| s.to_string()).collect(),
+        );
+        self
+    }
+
+    /// Restricts `tag`'s `style` attribute to the given CSS properties.
+    pub fn allowed_styles(mut self, tag: &str, properties: &[&str]) -> Self {
+        self.entry(tag).allowed_styles = properties.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// Sets `tag`'s per-element `class` allow-list.
+    pub fn allowed_classes(mut self, tag: &str, classes: &[&str]) -> Self {
+        self.entry(tag).allowed_classes = classes.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Sets `tag`'s per-element `class` block-list.
+    pub fn forbidden_classes(mut self, tag: &str, classes: &[&str]) -> Self {
+        self.entry(tag).forbidden_classes = classes.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Injects `attribute = value` on `tag` whenever it's missing after filtering.
+    pub fn default_attribute(mut self, tag: &str, attribute: &str, value: &str) -> Self {
+        self.entry(tag)
+            .default_attributes
+            .insert(attribute.to_string(), value.to_string());
+        self
+    }
+
+    /// Requires `attribute` to be present on `tag` after filtering, or the element's tag is
+    /// removed (see [`AllowedElement::required_attributes`]).
+    pub fn required_attribute(mut self, tag: &str, attribute: &str) -> Self {
+        self.entry(tag).required_attributes.push(attribute.to_string());
+        self
+    }
+
+    /// Whether to strip HTML comments. Defaults to `true`, matching [`Settings::default`].
+    pub fn remove_comments(mut self, remove_comments: bool) -> Self {
+        self.inner.remove_comments = remove_comments;
+        self
+    }
+
+    /// Global `class` allow-list, used by tags that don't set their own via [`Self::allowed_classes`].
+    pub fn global_allowed_classes(mut self, classes: &[&str]) -> Self {
+        self.inner.allowed_classes = classes.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Global `class` block-list, checked alongside any per-tag block-list.
+    pub fn global_forbidden_classes(mut self, classes: &[&str]) -> Self {
+        self.inner.forbidden_classes = classes.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Disallowed tags whose contents are dropped instead of unwrapped (see
+    /// [`Settings::remove_with_contents`]).
+    pub fn remove_with_contents(mut self, tags: &[&str]) -> Self {
+        self.inner.remove_with_contents = tags.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    /// Enables `rel`/`target` hardening on `<a>` elements (see [`Settings::enforce_link_safety`]).
+    pub fn enforce_link_safety(mut self, enforce: bool) -> Self {
+        self.inner.enforce_link_safety = enforce;
+        self
+    }
+
+    /// Frame targets permitted on `<a target="...">` when link safety is enforced (see
+    /// [`Settings::allowed_frame_targets`] for the `target` must-be-allowed caveat).
+    pub fn allowed_frame_targets(mut self, targets: &[&str]) -> Self {
+        self.inner.allowed_frame_targets = targets.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    /// Whether `id` attributes are honored at all (see [`Settings::enable_id`]). Defaults to `false`.
+    pub fn enable_id(mut self, enable_id: bool) -> Self {
+        self.inner.enable_id = enable_id;
+        self
+    }
+
+    /// Exact `id` values to reject (see [`Settings::id_blacklist`]).
+    pub fn id_blacklist(mut self, ids: &[&str]) -> Self {
+        self.inner.id_blacklist = ids.iter().map(|i| i.to_string()).collect();
+        self
+    }
+
+    /// Regex patterns checked against `id` values (see [`Settings::id_blacklist_regexp`]).
+    /// Compiled once, in [`Self::build`], so a reused [`Purifier`] never reparses a pattern
+    /// per element.
+    pub fn id_blacklist_regexp(mut self, patterns: &[&str]) -> Self {
+        self.id_blacklist_regexp = patterns.iter().map(|p| p.to_string()).collect();
+        self
+    }
+
+    /// Prepended to every surviving `id` (see [`Settings::id_prefix`]).
+    pub fn id_prefix(mut self, prefix: &str) -> Self {
+        self.inner.id_prefix = prefix.to_string();
+        self
+    }
+
+    /// Finishes building, returning the reusable [`Purifier`]. Invalid regex patterns are
+    /// dropped rather than rejecting every id.
+    pub fn build(mut self) -> Purifier {
+        self.inner.id_blacklist_regexp = self
+            .id_blacklist_regexp
+            .iter()
+            .filter_map(|pattern| Regex::new(pattern).ok())
+            .collect();
+        self.inner
+    }
 }
 
 #[cfg(test)]
@@ -183,7 +876,7 @@ mod tests {
         let output = purifier(input, settings);
         assert_eq!(
             output,
-            r#"<div><span style="color: black;"><a href="/test"><img src="/logo.png" />Rust</a></span></div>"#
+            r#"<div><span style="color: black;"><a href="/test"><img src="/logo.png" alt="" />Rust</a></span></div>"#
         );
     }
     #[test]
@@ -195,7 +888,7 @@ mod tests {
         let output = purifier(input, settings);
         assert_eq!(
             output,
-            r#"<div><span style="color: black;"><a href="/test"><img src="/logo.png" />Rust</a></span></div>"#
+            r#"<div><span style="color: black;"><a href="/test"><img src="/logo.png" alt="" />Rust</a></span></div>"#
         );
     }
     #[test]
@@ -208,7 +901,246 @@ mod tests {
         let output = purifier(input, settings);
         assert_eq!(
             output,
-            r#"<div><span style="color: black;"><!--Comment 1--><a href="/test"><img src="/logo.png" />Rust</a></span></div>"#
+            r#"<div><span style="color: black;"><!--Comment 1--><a href="/test"><img src="/logo.png" alt="" />Rust</a></span></div>"#
+        );
+    }
+    #[test]
+    fn test_purifier_strips_javascript_scheme() {
+        let settings = Settings {
+            ..Settings::default()
+        };
+        let input = r#"<a href="javascript:alert(1)">Click</a>"#;
+        let output = purifier(input, settings);
+        assert_eq!(output, r#"<a>Click</a>"#);
+    }
+    #[test]
+    fn test_purifier_allows_safe_data_image_src() {
+        let settings = Settings {
+            ..Settings::default()
+        };
+        let input = r#"<img src="data:image/png;base64,aGVsbG8="/>"#;
+        let output = purifier(input, settings);
+        assert_eq!(output, r#"<img src="data:image/png;base64,aGVsbG8=" alt="" />"#);
+    }
+    #[test]
+    fn test_purifier_strips_disallowed_style_properties() {
+        let settings = Settings {
+            ..Settings::default()
+        };
+        let input = r#"<span style="color: red; position: fixed;">Rust</span>"#;
+        let output = purifier(input, settings);
+        assert_eq!(output, r#"<span style="color: red;">Rust</span>"#);
+    }
+    #[test]
+    fn test_purifier_strips_unsafe_style_url() {
+        let settings = Settings {
+            ..Settings::default()
+        };
+        let input = r#"<span style="color: red; background-color: url(javascript:alert(1));">Rust</span>"#;
+        let output = purifier(input, settings);
+        assert_eq!(output, r#"<span style="color: red;">Rust</span>"#);
+    }
+    #[test]
+    fn test_purifier_keeps_style_value_with_embedded_semicolon_intact() {
+        let settings = Settings {
+            allowed: vec![AllowedElement {
+                name: "span",
+                attributes: vec!["style"],
+                protocols: HashMap::new(),
+                allowed_styles: vec!["background-image", "color"],
+                allowed_classes: vec![],
+                forbidden_classes: vec![],
+                default_attributes: HashMap::new(),
+                required_attributes: vec![],
+            }],
+            ..Settings::default()
+        };
+        let input = r#"<span style="background-image: url(http://example.com/img;v=2.png); color: red;">Rust</span>"#;
+        let output = purifier(input, settings);
+        assert_eq!(
+            output,
+            r#"<span style="background-image: url(http://example.com/img;v=2.png); color: red;">Rust</span>"#
         );
     }
+    #[test]
+    fn test_purifier_filters_class_allow_and_forbidden_lists() {
+        let settings = Settings {
+            allowed: vec![AllowedElement {
+                name: "span",
+                attributes: vec!["class"],
+                protocols: HashMap::new(),
+                allowed_styles: vec![],
+                allowed_classes: vec!["highlight", "muted"],
+                forbidden_classes: vec!["admin-only"],
+                default_attributes: HashMap::new(),
+                required_attributes: vec![],
+            }],
+            ..Settings::default()
+        };
+        let input = r#"<span class="highlight admin-only unknown">Rust</span>"#;
+        let output = purifier(input, settings);
+        assert_eq!(output, r#"<span class="highlight">Rust</span>"#);
+    }
+    #[test]
+    fn test_purifier_drops_script_contents() {
+        let settings = Settings {
+            ..Settings::default()
+        };
+        let input = r#"<div>before<script>alert(document.cookie)</script>after</div>"#;
+        let output = purifier(input, settings);
+        assert_eq!(output, r#"<div>beforeafter</div>"#);
+    }
+    #[test]
+    fn test_purifier_strips_unsafe_data_src() {
+        let settings = Settings {
+            ..Settings::default()
+        };
+        let input = r#"<img src="data:text/html,<script>alert(1)</script>"/>"#;
+        let output = purifier(input, settings);
+        assert_eq!(output, "");
+    }
+    #[test]
+    fn test_purifier_injects_default_alt_on_img() {
+        let settings = Settings {
+            ..Settings::default()
+        };
+        let input = r#"<img src="/logo.png"/>"#;
+        let output = purifier(input, settings);
+        assert_eq!(output, r#"<img src="/logo.png" alt="" />"#);
+    }
+    #[test]
+    fn test_purifier_enforces_link_safety() {
+        let settings = Settings {
+            enforce_link_safety: true,
+            ..Settings::default()
+        };
+        let input = r#"<a href="/test" target="_evil">Rust</a>"#;
+        let output = purifier(input, settings);
+        assert_eq!(output, r#"<a href="/test" rel="nofollow noopener">Rust</a>"#);
+    }
+    #[test]
+    fn test_purifier_restricts_allowed_frame_targets() {
+        let settings = Settings {
+            allowed: vec![AllowedElement {
+                name: "a",
+                attributes: vec!["href", "target"],
+                protocols: HashMap::new(),
+                allowed_styles: vec![],
+                allowed_classes: vec![],
+                forbidden_classes: vec![],
+                default_attributes: HashMap::new(),
+                required_attributes: vec![],
+            }],
+            enforce_link_safety: true,
+            ..Settings::default()
+        };
+        let input = r#"<a href="/test" target="_evil">Rust</a><a href="/test" target="_blank">Rust</a>"#;
+        let output = purifier(input, settings);
+        assert_eq!(
+            output,
+            r#"<a href="/test" rel="nofollow noopener">Rust</a><a href="/test" target="_blank" rel="nofollow noopener">Rust</a>"#
+        );
+    }
+    #[test]
+    fn test_purifier_strips_id_when_not_enabled() {
+        let settings = Settings {
+            allowed: vec![AllowedElement {
+                name: "div",
+                attributes: vec!["id"],
+                protocols: HashMap::new(),
+                allowed_styles: vec![],
+                allowed_classes: vec![],
+                forbidden_classes: vec![],
+                default_attributes: HashMap::new(),
+                required_attributes: vec![],
+            }],
+            ..Settings::default()
+        };
+        let input = r#"<div id="intro">Rust</div>"#;
+        let output = purifier(input, settings);
+        assert_eq!(output, r#"<div>Rust</div>"#);
+    }
+    #[test]
+    fn test_purifier_sanitizes_id_blacklist_regexp_and_prefix() {
+        let settings = Settings {
+            allowed: vec![AllowedElement {
+                name: "div",
+                attributes: vec!["id"],
+                protocols: HashMap::new(),
+                allowed_styles: vec![],
+                allowed_classes: vec![],
+                forbidden_classes: vec![],
+                default_attributes: HashMap::new(),
+                required_attributes: vec![],
+            }],
+            enable_id: true,
+            id_blacklist: vec!["header"],
+            id_blacklist_regexp: vec!["^admin-"],
+            id_prefix: "user-",
+            ..Settings::default()
+        };
+        let input = r#"<div id="header">A</div><div id="admin-panel">B</div><div id="intro">C</div>"#;
+        let output = purifier(input, settings);
+        assert_eq!(
+            output,
+            r#"<div>A</div><div>B</div><div id="user-intro">C</div>"#
+        );
+    }
+    #[test]
+    fn test_purifier_builder_strips_javascript_scheme() {
+        let purifier = Purifier::builder()
+            .allow("a", &["href"])
+            .url_schemes("a", "href", &["http", "https"])
+            .build();
+        let input = r#"<a href="javascript:alert(1)">Click</a>"#;
+        assert_eq!(purifier.purify(input), r#"<a>Click</a>"#);
+    }
+    #[test]
+    fn test_purifier_builder_reused_across_calls() {
+        let purifier = Purifier::builder().allow("b", &[]).build();
+        assert_eq!(purifier.purify("<b>one</b><i>two</i>"), "<b>one</b>two");
+        assert_eq!(purifier.purify("<b>three</b><i>four</i>"), "<b>three</b>four");
+    }
+    #[test]
+    fn test_purifier_builder_removes_comments_by_default() {
+        let purifier = Purifier::builder().allow("b", &[]).build();
+        assert_eq!(purifier.purify("<b>one<!--secret--></b>"), "<b>one</b>");
+    }
+    #[test]
+    fn test_purifier_builder_sanitizes_id() {
+        let purifier = Purifier::builder()
+            .allow("div", &["id"])
+            .enable_id(true)
+            .id_blacklist(&["header"])
+            .id_blacklist_regexp(&["^admin-"])
+            .id_prefix("user-")
+            .build();
+        let input = r#"<div id="header">A</div><div id="admin-panel">B</div><div id="intro">C</div>"#;
+        assert_eq!(
+            purifier.purify(input),
+            r#"<div>A</div><div>B</div><div id="user-intro">C</div>"#
+        );
+    }
+    #[test]
+    fn test_purifier_builder_purify_writer() {
+        let purifier = Purifier::builder().allow("b", &[]).build();
+        let mut output = Vec::new();
+        purifier.purify_writer("<b>one</b><i>two</i>", &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "<b>one</b>two");
+    }
+    struct FailingWriter;
+    impl std::io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("write failed"))
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    #[test]
+    fn test_purifier_builder_purify_writer_propagates_io_error() {
+        let purifier = Purifier::builder().allow("b", &[]).build();
+        let result = purifier.purify_writer("<b>one</b>", FailingWriter);
+        assert!(result.is_err());
+    }
 }